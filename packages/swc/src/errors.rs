@@ -0,0 +1,81 @@
+use swc_core::common::errors::HANDLER;
+use swc_core::common::Span;
+
+/// 上报「对象解构赋值」违规：`const { API_KEY } = process.env`
+pub fn report_destructuring_error(path: &str, span: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(
+                span,
+                &format!(
+                    "Direct destructuring of '{}' is not allowed. Use direct member access instead (e.g. '{}').",
+                    path
+                ),
+            )
+            .emit();
+    });
+}
+
+/// 上报「解构 + 可选链」违规：`const { API_KEY } = process?.env`
+pub fn report_destructuring_with_optional_error(path: &str, span: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(
+                span,
+                &format!(
+                    "Destructuring '{}' via optional chaining is not allowed. Use direct member access instead.",
+                    path
+                ),
+            )
+            .emit();
+    });
+}
+
+/// 上报「可选链访问」违规：`process.env?.API_KEY` / `process?.env`
+pub fn report_optional_chaining_error(path: &str, span: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(
+                span,
+                &format!(
+                    "Optional chaining access to '{}' is not allowed. Use direct member access instead.",
+                    path
+                ),
+            )
+            .emit();
+    });
+}
+
+/// 当 fix 阶段收集到的绑定标识符带有空 `SyntaxContext` 时上报：这通常意味着
+/// 宿主在调用本插件之前没有先跑 `resolver`，[`crate::fix::FixVisitor`] 用来
+/// 区分同名遮蔽变量的前提就不成立了。这种情况下我们选择整体跳过本次 fix
+/// （而不是按错误的假设继续重写，冒着静默改错作用域的风险），并提示用户
+/// 检查宿主的转换管线
+pub fn report_unresolved_program_warning() {
+    HANDLER.with(|handler| {
+        handler.warn(
+            "enforce-direct-access: skipping fix-mode rewrite because the program was not resolved \
+             (identifiers have an empty SyntaxContext); ensure the host runs `resolver` before invoking this plugin",
+        );
+    });
+}
+
+/// 当路径与某个配置项「接近但不完全一致」时，给出 "Did you mean ...?" 提示
+/// 例如 `process.evn.API_KEY` -> 建议 `process.env`
+///
+/// 这只是一个近似匹配的提示，不代表代码一定有问题（比如 `process.env.API_KEYS`
+/// 离配置的 `process.env.API_KEY` 很近，但完全是合法的无关代码），所以只上报
+/// 警告，不能像真正的违规那样阻断编译
+pub fn report_suggestion(path: &str, suggestion: &str, span: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                span,
+                &format!(
+                    "'{}' is not a recognized path. Did you mean '{}'?",
+                    path, suggestion
+                ),
+            )
+            .emit();
+    });
+}
@@ -0,0 +1,396 @@
+//! Second phase of `mode: "fix"`: rewrites identifiers that used to come
+//! from a now-removed destructuring declarator back into the direct
+//! member-access chain they were destructured from, e.g. `API_KEY` ->
+//! `process.env.API_KEY`.
+//!
+//! This visitor keys its rewrites off `Ident::to_id()`, i.e. `(Atom,
+//! SyntaxContext)`. That is only safe to do blindly if a `resolver` pass
+//! has already run over the program and assigned real, scope-aware
+//! `SyntaxContext`s — which is what distinguishes a top-level `API_KEY`
+//! from an unrelated `API_KEY` parameter shadowing it in a nested
+//! function. This plugin does not run `resolver` itself (re-resolving
+//! inside a plugin risks clobbering marks the host has already applied),
+//! so it relies on the host's swc pipeline having resolved the program
+//! before invoking this transform, which is the standard contract for
+//! swc plugins operating on already-parsed `Program`s.
+//!
+//! That contract is checked, not just assumed: [`FixVisitor::new`] refuses
+//! any binding whose `SyntaxContext` is still empty (the value every ident
+//! carries before `resolver` runs), because keying off it would conflate a
+//! destructured binding with an unrelated same-named ident shadowing it
+//! elsewhere. When that happens the whole fix pass is dropped for the file
+//! instead of risking a silent miscompile.
+
+use crate::errors::report_unresolved_program_warning;
+use std::collections::HashMap;
+use swc_core::common::{SyntaxContext, DUMMY_SP};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+/// 一条解构绑定对应的完整路径分段，例如 `["process", "env", "API_KEY"]`
+#[derive(Debug, Clone)]
+pub struct FixBinding {
+    pub segments: Vec<String>,
+}
+
+/// 把收集到的解构绑定重写回成员访问链，并清理因此变空的解构属性/声明器/语句
+pub struct FixVisitor {
+    bindings: HashMap<Id, FixBinding>,
+}
+
+impl FixVisitor {
+    pub fn new(bindings: HashMap<Id, FixBinding>) -> Self {
+        // 收集到的本地绑定理应带有 resolver 赋予的、非空的 SyntaxContext；
+        // 如果宿主没有在调用本插件前跑 resolver，所有 ident 都共享
+        // `SyntaxContext::empty()`，按 Id 查找就会无视作用域，把同名的
+        // 遮蔽变量也当成改写目标。一旦发现这种情况，整个 fix 直接退化为
+        // 空操作，而不是冒险按不成立的假设去重写代码
+        if bindings.keys().any(|(_, ctxt)| *ctxt == SyntaxContext::empty()) {
+            report_unresolved_program_warning();
+            return Self { bindings: HashMap::new() };
+        }
+
+        Self { bindings }
+    }
+
+    /// 由路径分段重建成员表达式链。分段是合法标识符时用 `a.b` 形式，否则
+    /// （比如正则/glob 匹配到的 `FOO-BAR` 这种路径）退化成 `a['b']` 形式的
+    /// 计算属性访问，避免拼出 `a.FOO-BAR` 这种非法 JS
+    fn build_member_chain(segments: &[String]) -> Expr {
+        let mut parts = segments.iter();
+        let root = parts.next().expect("fix binding must have at least one path segment");
+        let mut expr = Expr::Ident(Ident::new_no_ctxt(root.as_str().into(), DUMMY_SP));
+
+        for segment in parts {
+            let prop = if is_valid_member_ident(segment) {
+                MemberProp::Ident(IdentName::new(segment.as_str().into(), DUMMY_SP))
+            } else {
+                MemberProp::Computed(ComputedPropName {
+                    span: DUMMY_SP,
+                    expr: Box::new(Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: segment.as_str().into(),
+                        raw: None,
+                    }))),
+                })
+            };
+            expr = Expr::Member(MemberExpr { span: DUMMY_SP, obj: Box::new(expr), prop });
+        }
+
+        expr
+    }
+
+    /// 判断一个被解构出来的本地绑定是否被收集为待修复项
+    fn is_fixed_binding(&self, pat: &Pat) -> bool {
+        matches!(pat, Pat::Ident(binding_ident) if self.bindings.contains_key(&binding_ident.id.to_id()))
+    }
+}
+
+impl VisitMut for FixVisitor {
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        expr.visit_mut_children_with(self);
+
+        if let Expr::Ident(ident) = expr {
+            if let Some(binding) = self.bindings.get(&ident.to_id()) {
+                *expr = Self::build_member_chain(&binding.segments);
+            }
+        }
+    }
+
+    fn visit_mut_prop(&mut self, prop: &mut Prop) {
+        prop.visit_mut_children_with(self);
+
+        // 对象字面量里的简写属性（`{ API_KEY }`）是一个裸 `Ident`，不会被
+        // `visit_mut_expr` 访问到，需要单独改写成 `{ API_KEY: process.env.API_KEY }`
+        if let Prop::Shorthand(ident) = prop {
+            if let Some(binding) = self.bindings.get(&ident.to_id()) {
+                *prop = Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(IdentName::new(ident.sym.clone(), ident.span)),
+                    value: Box::new(Self::build_member_chain(&binding.segments)),
+                });
+            }
+        }
+    }
+
+    fn visit_mut_object_pat(&mut self, object_pat: &mut ObjectPat) {
+        object_pat.visit_mut_children_with(self);
+        // 只摘除已经被改写为成员访问的属性，未匹配配置路径（或带默认值而被
+        // 跳过）的属性原样保留，避免整条解构被错误地一并删除
+        object_pat.props.retain(|prop| match prop {
+            ObjectPatProp::KeyValue(kv) => !self.is_fixed_binding(&kv.value),
+            ObjectPatProp::Assign(assign) => {
+                !self.bindings.contains_key(&assign.key.id.to_id())
+            }
+            ObjectPatProp::Rest(_) => true,
+        });
+    }
+
+    fn visit_mut_var_declarators(&mut self, declarators: &mut Vec<VarDeclarator>) {
+        declarators.visit_mut_children_with(self);
+        // 只有当解构对象模式的全部属性都已被改写摘除、模式变空时，才删除
+        // 整个声明器；仍保留未匹配属性的声明器必须留在原地
+        declarators.retain(|declarator| !matches!(&declarator.name, Pat::Object(obj) if obj.props.is_empty()));
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        stmts.visit_mut_children_with(self);
+        // 删除解构全部被改写后不再有任何声明器的 `var`/`let`/`const` 语句
+        stmts.retain(|stmt| !is_emptied_var_decl_stmt(stmt));
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        items.visit_mut_children_with(self);
+        items.retain(|item| match item {
+            ModuleItem::Stmt(stmt) => !is_emptied_var_decl_stmt(stmt),
+            // `export const { API_KEY } = process.env` 清空后是
+            // `ModuleDecl::ExportDecl`，而不是 `Stmt`，需要单独处理，否则会
+            // 残留一个非法的 `export const;`
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+                !matches!(&export_decl.decl, Decl::Var(var_decl) if var_decl.decls.is_empty())
+            }
+            _ => true,
+        });
+    }
+}
+
+fn is_emptied_var_decl_stmt(stmt: &Stmt) -> bool {
+    matches!(stmt, Stmt::Decl(Decl::Var(var_decl)) if var_decl.decls.is_empty())
+}
+
+/// 判断字符串是否是一个只由 ASCII 字母、数字、`_`、`$` 组成的合法 JS 标识符
+/// （含非 ASCII 字符的标识符一律按不合法处理），决定 [`FixVisitor::build_member_chain`]
+/// 把某个 path 分段重建成 `a.b` 还是 `a['b']`
+fn is_valid_member_ident(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c == '$' || c.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+    chars.all(|c| c == '_' || c == '$' || c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_core::common::{Globals, Mark, GLOBALS};
+
+    fn ident(name: &str) -> Ident {
+        Ident::new_no_ctxt(name.into(), DUMMY_SP)
+    }
+
+    /// 一个真正的 fix 绑定必须带有 resolver 赋予的非空 `SyntaxContext`，
+    /// 否则会被 [`FixVisitor::new`] 的守卫判定为"程序未 resolve"而整体跳过，
+    /// 所以需要一个能生成非空 ctxt 的 ident 供这些测试使用
+    fn resolved_ident(name: &str, ctxt: SyntaxContext) -> Ident {
+        Ident::new(name.into(), DUMMY_SP, ctxt)
+    }
+
+    /// 在一个临时的 `Globals` 里分配一个非空 `SyntaxContext`，模拟 resolver
+    /// 跑过之后的 ident
+    fn with_resolved_ctxt<T>(f: impl FnOnce(SyntaxContext) -> T) -> T {
+        GLOBALS.set(&Globals::new(), || f(SyntaxContext::empty().apply_mark(Mark::new())))
+    }
+
+    fn binding(segments: &[&str]) -> FixBinding {
+        FixBinding { segments: segments.iter().map(|s| s.to_string()).collect() }
+    }
+
+    fn key_value_prop(name: &str) -> ObjectPatProp {
+        ObjectPatProp::KeyValue(KeyValuePatProp {
+            key: PropName::Ident(IdentName::new(name.into(), DUMMY_SP)),
+            value: Box::new(Pat::Ident(BindingIdent { id: ident(name), type_ann: None })),
+        })
+    }
+
+    fn resolved_key_value_prop(name: &str, ctxt: SyntaxContext) -> ObjectPatProp {
+        ObjectPatProp::KeyValue(KeyValuePatProp {
+            key: PropName::Ident(IdentName::new(name.into(), DUMMY_SP)),
+            value: Box::new(Pat::Ident(BindingIdent { id: resolved_ident(name, ctxt), type_ann: None })),
+        })
+    }
+
+    fn assign_prop(name: &str, default: Option<Expr>) -> ObjectPatProp {
+        ObjectPatProp::Assign(AssignPatProp {
+            span: DUMMY_SP,
+            key: BindingIdent { id: ident(name), type_ann: None },
+            value: default.map(Box::new),
+        })
+    }
+
+    #[test]
+    fn rewrites_ident_to_member_chain() {
+        with_resolved_ctxt(|ctxt| {
+            let mut bindings = HashMap::new();
+            bindings.insert(resolved_ident("API_KEY", ctxt).to_id(), binding(&["process", "env", "API_KEY"]));
+            let mut visitor = FixVisitor::new(bindings);
+
+            let mut expr = Expr::Ident(resolved_ident("API_KEY", ctxt));
+            visitor.visit_mut_expr(&mut expr);
+
+            match expr {
+                Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop), .. }) => {
+                    assert_eq!(prop.sym.as_str(), "API_KEY");
+                    match *obj {
+                        Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop), .. }) => {
+                            assert_eq!(prop.sym.as_str(), "env");
+                            assert!(matches!(*obj, Expr::Ident(root) if root.sym.as_str() == "process"));
+                        }
+                        other => panic!("expected nested member expr, got {other:?}"),
+                    }
+                }
+                other => panic!("expected member expr, got {other:?}"),
+            }
+        });
+    }
+
+    #[test]
+    fn leaves_unrelated_ident_untouched() {
+        let bindings = HashMap::new();
+        let mut visitor = FixVisitor::new(bindings);
+
+        let mut expr = Expr::Ident(ident("PORT"));
+        visitor.visit_mut_expr(&mut expr);
+
+        assert!(matches!(expr, Expr::Ident(ident) if ident.sym.as_str() == "PORT"));
+    }
+
+    #[test]
+    fn empty_syntax_context_disables_fix_entirely() {
+        // If the host never ran `resolver`, every ident shares
+        // `SyntaxContext::empty()`, so keying off `Id` can't tell a
+        // destructured binding apart from an unrelated shadowing ident of
+        // the same name. FixVisitor must refuse to rewrite in that case
+        // instead of risking a silent miscompile.
+        let mut bindings = HashMap::new();
+        bindings.insert(ident("API_KEY").to_id(), binding(&["process", "env", "API_KEY"]));
+        let mut visitor = FixVisitor::new(bindings);
+
+        let mut expr = Expr::Ident(ident("API_KEY"));
+        visitor.visit_mut_expr(&mut expr);
+
+        assert!(matches!(expr, Expr::Ident(ident) if ident.sym.as_str() == "API_KEY"));
+    }
+
+    #[test]
+    fn rewrites_shorthand_object_literal_prop() {
+        // `const cfg = { API_KEY }` — the shorthand prop is a bare Ident,
+        // not an Expr::Ident, and needs its own rewrite hook.
+        with_resolved_ctxt(|ctxt| {
+            let mut bindings = HashMap::new();
+            bindings.insert(resolved_ident("API_KEY", ctxt).to_id(), binding(&["process", "env", "API_KEY"]));
+            let mut visitor = FixVisitor::new(bindings);
+
+            let mut prop = Prop::Shorthand(resolved_ident("API_KEY", ctxt));
+            visitor.visit_mut_prop(&mut prop);
+
+            match prop {
+                Prop::KeyValue(kv) => {
+                    assert!(matches!(&kv.key, PropName::Ident(key) if key.sym.as_str() == "API_KEY"));
+                    assert!(matches!(*kv.value, Expr::Member(_)));
+                }
+                other => panic!("expected rewritten key-value prop, got {other:?}"),
+            }
+        });
+    }
+
+    #[test]
+    fn partial_destructuring_only_removes_fixed_props() {
+        // const { API_KEY, PORT } = process.env — only API_KEY is configured,
+        // so PORT must survive in the object pattern untouched.
+        with_resolved_ctxt(|ctxt| {
+            let mut bindings = HashMap::new();
+            bindings.insert(resolved_ident("API_KEY", ctxt).to_id(), binding(&["process", "env", "API_KEY"]));
+            let mut visitor = FixVisitor::new(bindings);
+
+            let mut object_pat = ObjectPat {
+                span: DUMMY_SP,
+                props: vec![resolved_key_value_prop("API_KEY", ctxt), key_value_prop("PORT")],
+                optional: false,
+                type_ann: None,
+            };
+            visitor.visit_mut_object_pat(&mut object_pat);
+
+            assert_eq!(object_pat.props.len(), 1);
+            assert!(matches!(
+                &object_pat.props[0],
+                ObjectPatProp::KeyValue(kv) if matches!(&*kv.value, Pat::Ident(b) if b.id.sym.as_str() == "PORT")
+            ));
+        });
+    }
+
+    #[test]
+    fn defaulted_shorthand_prop_is_never_removed() {
+        // const { API_KEY = 'default' } = process.env must be left alone even
+        // though API_KEY matches a configured path, because it was never
+        // collected into `bindings` (see
+        // transform::EnforceDirectAccessTransformer::handle_destructuring).
+        let bindings = HashMap::new();
+        let mut visitor = FixVisitor::new(bindings);
+
+        let mut object_pat = ObjectPat {
+            span: DUMMY_SP,
+            props: vec![assign_prop(
+                "API_KEY",
+                Some(Expr::Lit(Lit::Str(Str { span: DUMMY_SP, value: "default".into(), raw: None }))),
+            )],
+            optional: false,
+            type_ann: None,
+        };
+        visitor.visit_mut_object_pat(&mut object_pat);
+
+        assert_eq!(object_pat.props.len(), 1);
+    }
+
+    #[test]
+    fn declarator_removed_only_when_pattern_fully_emptied() {
+        let fully_emptied = VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Object(ObjectPat { span: DUMMY_SP, props: vec![], optional: false, type_ann: None }),
+            init: None,
+            definite: false,
+        };
+        let partially_emptied = VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Object(ObjectPat {
+                span: DUMMY_SP,
+                props: vec![key_value_prop("PORT")],
+                optional: false,
+                type_ann: None,
+            }),
+            init: None,
+            definite: false,
+        };
+
+        let mut visitor = FixVisitor::new(HashMap::new());
+        let mut declarators = vec![fully_emptied.clone(), partially_emptied.clone()];
+        visitor.visit_mut_var_declarators(&mut declarators);
+
+        assert_eq!(declarators.len(), 1);
+        assert!(matches!(&declarators[0].name, Pat::Object(obj) if obj.props.len() == 1));
+
+        // sanity: an already-empty pattern alone is dropped entirely
+        let mut only_emptied = vec![fully_emptied];
+        visitor.visit_mut_var_declarators(&mut only_emptied);
+        assert!(only_emptied.is_empty());
+    }
+
+    #[test]
+    fn non_identifier_segment_falls_back_to_computed_access() {
+        // A regex/glob path can match a leaf that isn't a valid JS
+        // identifier (e.g. `process.env['FOO-BAR']`, canonicalized to the
+        // segment "FOO-BAR"). Rebuilding it as `a.FOO-BAR` would emit
+        // invalid JS, so it must fall back to `a['FOO-BAR']`.
+        let expr = FixVisitor::build_member_chain(&binding(&["process", "env", "FOO-BAR"]).segments);
+
+        match expr {
+            Expr::Member(MemberExpr { obj, prop: MemberProp::Computed(computed), .. }) => {
+                assert!(matches!(
+                    &*computed.expr,
+                    Expr::Lit(Lit::Str(s)) if s.value.as_str() == "FOO-BAR"
+                ));
+                assert!(matches!(*obj, Expr::Member(_)));
+            }
+            other => panic!("expected computed member expr, got {other:?}"),
+        }
+    }
+}
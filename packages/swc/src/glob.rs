@@ -0,0 +1,81 @@
+//! Glob-style matching for dotted config paths, e.g. `process.env.*` or
+//! `import.meta.**`.
+//!
+//! Patterns and paths are split on `.` into component vectors (mirroring
+//! `std::path::Components` iteration) and compared component-by-component:
+//! `*` matches exactly one component, `**` matches zero-or-more remaining
+//! components (greedy, with backtracking), and any other token must match
+//! literally.
+
+/// 判断某个配置 pattern 是否包含通配符
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.split('.').any(|segment| segment == "*" || segment == "**")
+}
+
+/// 判断 `path` 是否匹配 glob `pattern`
+pub fn matches(pattern: &str, path: &str) -> bool {
+    let pattern_parts: Vec<&str> = pattern.split('.').collect();
+    let path_parts: Vec<&str> = path.split('.').collect();
+    matches_components(&pattern_parts, &path_parts)
+}
+
+fn matches_components(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+
+        // `**`：贪婪匹配零个或多个剩余分段，回溯直到剩余 pattern 能匹配上
+        Some((&"**", rest)) => (0..=path.len())
+            .rev()
+            .any(|consumed| matches_components(rest, &path[consumed..])),
+
+        // `*`：匹配且仅匹配一个分段
+        Some((&"*", rest)) => match path.split_first() {
+            Some((_, path_rest)) => matches_components(rest, path_rest),
+            None => false,
+        },
+
+        // 字面量分段：必须完全相等
+        Some((token, rest)) => match path.split_first() {
+            Some((head, path_rest)) if head == token => matches_components(rest, path_rest),
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_glob_detects_wildcards() {
+        assert!(is_glob("process.env.*"));
+        assert!(is_glob("import.meta.**"));
+        assert!(!is_glob("process.env.API_KEY"));
+    }
+
+    #[test]
+    fn single_star_matches_exactly_one_segment() {
+        assert!(matches("process.env.*", "process.env.API_KEY"));
+        assert!(!matches("process.env.*", "process.env"));
+        assert!(!matches("process.env.*", "process.env.API_KEY.nested"));
+    }
+
+    #[test]
+    fn double_star_matches_zero_or_more_segments() {
+        assert!(matches("process.env.**", "process.env"));
+        assert!(matches("process.env.**", "process.env.API_KEY"));
+        assert!(matches("process.env.**", "process.env.API_KEY.nested"));
+    }
+
+    #[test]
+    fn double_star_backtracks_past_literal_suffix() {
+        assert!(matches("process.**.API_KEY", "process.env.nested.API_KEY"));
+        assert!(!matches("process.**.API_KEY", "process.env.nested.PORT"));
+    }
+
+    #[test]
+    fn literal_segments_must_match_exactly() {
+        assert!(!matches("process.env.API_KEY", "process.env.PORT"));
+        assert!(matches("process.env.API_KEY", "process.env.API_KEY"));
+    }
+}
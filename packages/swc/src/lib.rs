@@ -1,18 +1,34 @@
-use swc_core::ecma::{
-    ast::Program,
-    visit::{as_folder, FoldWith, VisitMut},
-};
+use swc_core::ecma::visit::VisitMutWith;
+use swc_core::ecma::ast::Program;
 use swc_core::plugin::{plugin_transform, proxies::TransformPluginProgramMetadata};
 
+mod errors;
+mod fix;
+mod glob;
+mod suggest;
 mod transform;
 
-pub struct TransformVisitor;
-
-impl VisitMut for TransformVisitor {
-    // Plugin implementation will be added later
-}
+use fix::FixVisitor;
+use transform::{EnforceDirectAccessTransformer, Mode, PluginConfig};
 
 #[plugin_transform]
-pub fn process_transform(program: Program, _metadata: TransformPluginProgramMetadata) -> Program {
-    program.fold_with(&mut as_folder(TransformVisitor))
+pub fn process_transform(mut program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+    let config: PluginConfig = metadata
+        .get_transform_plugin_config()
+        .map(|raw| {
+            serde_json::from_str(&raw).expect("invalid enforce-direct-access plugin config")
+        })
+        .unwrap_or_default();
+
+    let mode = config.mode;
+    let mut transformer = EnforceDirectAccessTransformer::new(config)
+        .expect("invalid enforce-direct-access plugin config");
+    program.visit_mut_with(&mut transformer);
+
+    if mode == Mode::Fix {
+        let bindings = transformer.take_fix_data();
+        program.visit_mut_with(&mut FixVisitor::new(bindings));
+    }
+
+    program
 }
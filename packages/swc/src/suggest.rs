@@ -0,0 +1,108 @@
+//! "Did you mean ...?" suggestions for near-miss configured paths.
+//!
+//! Mirrors rustc's `find_best_match_for_name`: candidates are ranked by
+//! Levenshtein edit distance and only accepted when the distance is at most
+//! a length-relative threshold, so wildly different paths never produce a misleading
+//! suggestion.
+
+/// 经典的双行动态规划 Levenshtein 编辑距离
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// 在 `candidates` 中查找与 `path` 编辑距离最小的条目。
+///
+/// 只有当距离落在 `max(path.len(), candidate.len()) / 3` 的阈值内才会被
+/// 采纳（移植自 rustc），且当该阈值为 0 时直接跳过，避免对完全无关的
+/// 短路径给出误导性建议。
+pub fn find_best_match_for_path<'a, I>(path: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut best: Option<(&'a str, usize)> = None;
+
+    for candidate in candidates {
+        let threshold = path.len().max(candidate.len()) / 3;
+        if threshold == 0 {
+            continue;
+        }
+
+        let distance = levenshtein_distance(path, candidate);
+        if distance == 0 || distance > threshold {
+            continue;
+        }
+
+        if best.map_or(true, |(_, best_distance)| distance < best_distance) {
+            best = Some((candidate.as_str(), distance));
+        }
+    }
+
+    best.map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_distance_handles_equal_and_empty_strings() {
+        assert_eq!(levenshtein_distance("env", "env"), 0);
+        assert_eq!(levenshtein_distance("", "env"), 3);
+        assert_eq!(levenshtein_distance("env", ""), 3);
+    }
+
+    #[test]
+    fn levenshtein_distance_counts_multiple_edits() {
+        assert_eq!(levenshtein_distance("process.evn", "process.env"), 2);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn find_best_match_suggests_near_miss() {
+        let candidates = vec!["process.env".to_string(), "import.meta".to_string()];
+        assert_eq!(
+            find_best_match_for_path("process.evn", &candidates),
+            Some("process.env")
+        );
+    }
+
+    #[test]
+    fn find_best_match_rejects_exact_match() {
+        let candidates = vec!["process.env".to_string()];
+        assert_eq!(find_best_match_for_path("process.env", &candidates), None);
+    }
+
+    #[test]
+    fn find_best_match_rejects_unrelated_paths() {
+        let candidates = vec!["import.meta".to_string()];
+        assert_eq!(find_best_match_for_path("process.env", &candidates), None);
+    }
+
+    #[test]
+    fn find_best_match_picks_closest_of_several_candidates() {
+        let candidates = vec![
+            "process.enviro".to_string(),
+            "process.env".to_string(),
+            "import.meta".to_string(),
+        ];
+        assert_eq!(
+            find_best_match_for_path("process.evn", &candidates),
+            Some("process.env")
+        );
+    }
+}
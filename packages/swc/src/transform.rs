@@ -1,39 +1,188 @@
 use crate::errors::{
     report_destructuring_error, report_destructuring_with_optional_error,
-    report_optional_chaining_error,
+    report_optional_chaining_error, report_suggestion,
 };
+use crate::fix::FixBinding;
+use crate::glob;
+use crate::suggest::find_best_match_for_path;
+use regex::Regex;
 use serde::Deserialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use swc_core::common::Span;
 use swc_core::ecma::{
     ast::*,
     visit::{VisitMut, VisitMutWith},
 };
 
+/// 插件的运行模式：`report` 只上报诊断；`fix` 把违规的纯解构（init 不含
+/// 可选链）重写回直接成员访问，并且不再为这类解构上报错误。fix 模式下
+/// 无法改写的解构属性（带默认值、含 `...rest`、嵌套解构）既不上报也不改写；
+/// 可选链访问以及经可选链的解构在两种模式下都照常上报
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Mode {
+    #[default]
+    Report,
+    Fix,
+}
+
+/// 正则 kind 标记，目前只有一种取值，预留给未来扩展其它结构化 path 类型
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathEntryKind {
+    Regex,
+}
+
+/// `paths` 数组的单个条目：要么是普通字符串（精确路径或 glob 模式），
+/// 要么是结构化的正则条目 `{ pattern, kind: "regex" }`
 #[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum PathEntry {
+    Regex { pattern: String, kind: PathEntryKind },
+    Plain(String),
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct PluginConfig {
     /// 要检查的对象路径数组
     /// 例如：["process.env", "import.meta.env"]
+    /// 支持 glob 通配符：`*` 匹配单个分段，`**` 匹配零个或多个分段
+    /// 例如："process.env.*" 匹配 process.env 下任意一个 key，
+    /// "import.meta.**" 匹配 import.meta 下任意深度的路径
+    /// 也支持结构化的正则条目，按命名约定批量禁止一类变量：
+    /// `{ "pattern": "^process\\.env\\..*_KEY$", "kind": "regex" }`
+    #[serde(default)]
+    pub paths: Vec<PathEntry>,
+    /// 运行模式，默认 `report`，设为 `fix` 时开启自动修复
     #[serde(default)]
-    pub paths: Vec<String>,
+    pub mode: Mode,
+}
+
+/// 把字符串字面量的内容解析为路径分段
+fn decode_str_segment(value: &str) -> Option<String> {
+    Some(value.to_string())
+}
+
+/// 把字面量计算属性（`obj['prop']` / `obj[0]`）解析为路径分段，
+/// 动态表达式（变量、函数调用等）返回 `None`
+fn computed_prop_segment(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => decode_str_segment(&str_lit.value),
+        Expr::Lit(Lit::Num(num_lit)) => Some(num_lit.value.to_string()),
+        _ => None,
+    }
+}
+
+/// 把解构 key（`PropName`）解析为路径分段，覆盖 `{ a: b }`、`{ 'a': b }`、
+/// `{ 0: b }` 三种形式
+fn prop_name_to_segment(prop_name: &PropName) -> Option<String> {
+    match prop_name {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(str_prop) => decode_str_segment(&str_prop.value),
+        PropName::Num(num_prop) => Some(num_prop.value.to_string()),
+        _ => None,
+    }
+}
+
+/// [`PluginConfig::validate`] 编译后的内部表示，供 [`EnforceDirectAccessTransformer::new`]
+/// 直接消费，避免正则被编译两遍
+struct CompiledPaths {
+    config_paths: HashSet<String>,
+    glob_patterns: Vec<String>,
+    regexes: Vec<Regex>,
+    mode: Mode,
 }
 
 impl PluginConfig {
-    pub fn validate(&self) -> Result<(), String> {
-        // 允许 paths 为空，此时插件不执行任何检查
-        // 这与 Babel 插件的行为保持一致
-        Ok(())
+    /// 校验并编译配置里的所有 path 条目，是 path 合法性检查的唯一入口。
+    /// 目前唯一会失败的是非法的正则 pattern；编译结果会被
+    /// [`EnforceDirectAccessTransformer::new`] 直接复用，不会重复编译
+    fn validate(self) -> Result<CompiledPaths, String> {
+        let mode = self.mode;
+        let mut config_paths = HashSet::new();
+        let mut glob_patterns = Vec::new();
+        let mut regexes = Vec::new();
+
+        for entry in self.paths {
+            match entry {
+                PathEntry::Regex { pattern, .. } => {
+                    let compiled = Regex::new(&pattern)
+                        .map_err(|err| format!("invalid regex pattern '{}': {}", pattern, err))?;
+                    regexes.push(compiled);
+                }
+                PathEntry::Plain(path) => {
+                    if glob::is_glob(&path) {
+                        glob_patterns.push(path);
+                    } else {
+                        config_paths.insert(path);
+                    }
+                }
+            }
+        }
+
+        Ok(CompiledPaths { config_paths, glob_patterns, regexes, mode })
     }
 }
 
 pub struct EnforceDirectAccessTransformer {
+    // 精确匹配的路径，走快速路径（HashSet 查找）
     config_paths: HashSet<String>,
+    // 含通配符（`*` / `**`）的 glob 模式，按需逐个匹配
+    glob_patterns: Vec<String>,
+    // 预编译的正则 path，按命名约定批量匹配
+    regexes: Vec<Regex>,
+    mode: Mode,
+    // fix 模式下收集到的绑定：本地标识符 -> 完整路径分段。
+    // 第二阶段 FixVisitor 依据这张表，逐个属性地把解构模式里已经
+    // 改写成员访问的绑定从 ObjectPat 里摘掉；没有被收录的绑定
+    // （嵌套解构、带默认值等不支持自动修复的情形）原样保留
+    fix_bindings: HashMap<Id, FixBinding>,
 }
 
 impl EnforceDirectAccessTransformer {
-    pub fn new(config: PluginConfig) -> Self {
-        Self { config_paths: config.paths.into_iter().collect() }
+    /// 通过 [`PluginConfig::validate`] 校验并编译配置，这是唯一的编译路径——
+    /// 正则只会被编译一次
+    pub fn new(config: PluginConfig) -> Result<Self, String> {
+        let CompiledPaths { config_paths, glob_patterns, regexes, mode } = config.validate()?;
+        Ok(Self { config_paths, glob_patterns, regexes, mode, fix_bindings: HashMap::new() })
+    }
+
+    /// 取出 fix 阶段收集到的数据，供第二阶段的 [`crate::fix::FixVisitor`] 使用
+    pub fn take_fix_data(&mut self) -> HashMap<Id, FixBinding> {
+        std::mem::take(&mut self.fix_bindings)
+    }
+
+    /// 收集一条 fix 绑定：把解构出的本地标识符记录为「完整路径分段」。
+    /// 只处理简单的 `Pat::Ident`（含重命名），嵌套解构不在自动修复范围内，
+    /// 返回是否成功收集。对应的 `ObjectPatProp` 是否从解构模式里摘除，由
+    /// [`crate::fix::FixVisitor`] 按收集到的绑定表判断（没有收录的属性，
+    /// 比如嵌套解构，原样保留）
+    fn collect_fix_binding(&mut self, local: &Pat, full_path: &str) -> bool {
+        if let Pat::Ident(binding_ident) = local {
+            let segments = full_path.split('.').map(str::to_string).collect();
+            self.fix_bindings.insert(binding_ident.id.to_id(), FixBinding { segments });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 判断 `path` 是否匹配任意配置项（精确匹配优先，其次 glob，最后正则），
+    /// 返回命中的配置项本身，用于诊断信息
+    fn matched_config_path(&self, path: &str) -> Option<&str> {
+        if let Some(exact) = self.config_paths.get(path) {
+            return Some(exact.as_str());
+        }
+        if let Some(pattern) = self.glob_patterns.iter().find(|pattern| glob::matches(pattern, path)) {
+            return Some(pattern.as_str());
+        }
+        self.regexes.iter().find(|regex| regex.is_match(path)).map(|regex| regex.as_str())
+    }
+
+    /// 是否存在任何配置（精确、glob 或正则）
+    fn has_config(&self) -> bool {
+        !self.config_paths.is_empty() || !self.glob_patterns.is_empty() || !self.regexes.is_empty()
     }
 
     /// 构建表达式的完整路径
@@ -45,31 +194,40 @@ impl EnforceDirectAccessTransformer {
 
         loop {
             match current {
-                // 普通成员访问：obj.prop
+                // 普通成员访问：obj.prop / obj['prop']
                 Expr::Member(member) => {
                     if let MemberProp::Ident(ident) = &member.prop {
                         parts.insert(0, ident.sym.to_string());
                         current = &member.obj;
-                    } else if let MemberProp::Computed(_computed) = &member.prop {
-                        // 计算属性：obj['prop']
-                        // Note: We currently don't support computed properties
-                        // This could be enhanced in the future
-                        return None;
+                    } else if let MemberProp::Computed(computed) = &member.prop {
+                        match computed_prop_segment(&computed.expr) {
+                            Some(segment) => {
+                                parts.insert(0, segment);
+                                current = &member.obj;
+                            }
+                            // 动态计算属性（非字符串/数字字面量）无法静态解析，保持跳过
+                            None => return None,
+                        }
                     } else {
                         return None;
                     }
                 }
 
-                // 可选链成员访问：obj?.prop
+                // 可选链成员访问：obj?.prop / obj?.['prop']
                 Expr::OptChain(opt_chain) => {
                     has_optional = true;
                     if let OptChainBase::Member(member) = &*opt_chain.base {
                         if let MemberProp::Ident(ident) = &member.prop {
                             parts.insert(0, ident.sym.to_string());
                             current = &member.obj;
-                        } else if let MemberProp::Computed(_computed) = &member.prop {
-                            // Currently don't support computed properties
-                            return None;
+                        } else if let MemberProp::Computed(computed) = &member.prop {
+                            match computed_prop_segment(&computed.expr) {
+                                Some(segment) => {
+                                    parts.insert(0, segment);
+                                    current = &member.obj;
+                                }
+                                None => return None,
+                            }
                         } else {
                             return None;
                         }
@@ -117,7 +275,7 @@ impl EnforceDirectAccessTransformer {
     /// 处理可选链表达式
     fn handle_optional_chain_expr(&self, expr: &Expr, span: Span) {
         // 如果没有配置路径，不执行检查
-        if self.config_paths.is_empty() {
+        if !self.has_config() {
             return;
         }
 
@@ -129,88 +287,133 @@ impl EnforceDirectAccessTransformer {
                 // 构建 object 部分的路径
                 if let Some((object_path, _)) = self.build_expression_path(&member.obj) {
                     // 获取 property 名称，构建完整路径
-                    let property_name = if let MemberProp::Ident(ident) = &member.prop {
-                        Some(ident.sym.to_string())
-                    } else {
-                        None
+                    let property_name = match &member.prop {
+                        MemberProp::Ident(ident) => Some(ident.sym.to_string()),
+                        MemberProp::Computed(computed) => computed_prop_segment(&computed.expr),
+                        _ => None,
                     };
 
                     let full_path = property_name.as_ref().map(|prop| format!("{}.{}", object_path, prop));
 
-                    // 检查 object 路径或完整路径是否匹配配置
+                    // 检查 object 路径或完整路径是否匹配配置（精确、glob 或正则）
                     // 规则：
-                    // 1. object 精确匹配：process.env?.API_KEY
-                    // 2. fullPath 精确匹配：process?.env
-                    for config_path in &self.config_paths {
-                        if &object_path == config_path {
-                            // object 精确匹配：process.env?.API_KEY
+                    // 1. object 匹配：process.env?.API_KEY
+                    // 2. fullPath 匹配：process?.env
+                    if let Some(config_path) = self.matched_config_path(&object_path) {
+                        // object 匹配：process.env?.API_KEY
+                        report_optional_chaining_error(config_path, span);
+                        return;
+                    }
+                    if let Some(ref full) = full_path {
+                        if let Some(config_path) = self.matched_config_path(full) {
+                            // fullPath 匹配：process?.env
                             report_optional_chaining_error(config_path, span);
                             return;
-                        } else if let Some(ref full) = full_path {
-                            if full == config_path {
-                                // fullPath 精确匹配：process?.env
-                                report_optional_chaining_error(config_path, span);
-                                return;
-                            }
                         }
-                        // 如果 object 或 fullPath 是配置路径的子路径，不报错
-                        // 例如：process.env.API_KEY?.toLowerCase() (配置是 process.env)
+                    }
+                    // 如果 object 或 fullPath 是配置路径的子路径，不报错
+                    // 例如：process.env.API_KEY?.toLowerCase() (配置是 process.env)
+
+                    // 没有精确匹配：尝试给出 "Did you mean ...?" 提示
+                    // 优先在完整路径上找近似匹配，找不到再退化到 object 路径
+                    let suggestion = full_path
+                        .as_deref()
+                        .and_then(|full| find_best_match_for_path(full, &self.config_paths).map(|s| (full, s)))
+                        .or_else(|| {
+                            find_best_match_for_path(&object_path, &self.config_paths)
+                                .map(|s| (object_path.as_str(), s))
+                        });
+                    if let Some((path, suggestion)) = suggestion {
+                        report_suggestion(path, suggestion, span);
                     }
                 }
             }
         }
     }
 
+    /// 在 `config_paths` 中查找与 `path` 接近的条目，找到则上报 "Did you mean ...?"
+    fn suggest_for_path(&self, path: &str, span: Span) {
+        if let Some(suggestion) = find_best_match_for_path(path, &self.config_paths) {
+            report_suggestion(path, suggestion, span);
+        }
+    }
+
     /// 处理解构模式
-    fn handle_destructuring(&self, pat: &Pat, init: &Expr, span: Span) {
+    fn handle_destructuring(&mut self, pat: &Pat, init: &Expr, span: Span) {
         // 如果没有配置路径，不执行检查
-        if self.config_paths.is_empty() {
+        if !self.has_config() {
             return;
         }
 
         // 只处理对象解构
-        if let Pat::Object(object_pat) = pat {
-            // 构建 init 表达式的路径
-            if let Some((init_path, has_optional)) = self.build_expression_path(init) {
-                // Pattern 2: 如果 init 使用了可选链，检查 init 路径本身是否匹配
-                if has_optional && self.config_paths.contains(&init_path) {
-                    report_destructuring_with_optional_error(&init_path, span);
-                    return;
-                }
+        let object_pat = match pat {
+            Pat::Object(object_pat) => object_pat,
+            _ => return,
+        };
+
+        // 构建 init 表达式的路径
+        let (init_path, has_optional) = match self.build_expression_path(init) {
+            Some(result) => result,
+            None => return,
+        };
 
-                // Pattern 3: 检查 init + 属性名的组合是否匹配配置路径
-                for prop in &object_pat.props {
-                    if let ObjectPatProp::KeyValue(kv) = prop {
-                        // 获取属性名
-                        let property_name = match &kv.key {
-                            PropName::Ident(ident) => Some(ident.sym.to_string()),
-                            // Currently don't support string literal keys due to Wtf8 complexity
-                            _ => None,
-                        };
-
-                        if let Some(property_name) = property_name {
-                            // 组合完整路径：init 路径 + 属性名
-                            let full_path = format!("{}.{}", init_path, property_name);
-
-                            // 检查是否匹配配置的路径
-                            if self.config_paths.contains(&full_path) {
-                                // Pattern 3: 纯解构（init 不含可选链）
-                                if !has_optional {
-                                    report_destructuring_error(&full_path, span);
+        // Pattern 2: 如果 init 使用了可选链，检查 init 路径本身是否匹配
+        if has_optional && self.matched_config_path(&init_path).is_some() {
+            report_destructuring_with_optional_error(&init_path, span);
+            return;
+        }
+
+        // 含 `...rest` 的解构不参与自动修复：摘除某个属性后，`rest` 会开始
+        // 捕获到它，改变 `rest` 的内容，属于不安全的重写
+        let has_rest = object_pat.props.iter().any(|prop| matches!(prop, ObjectPatProp::Rest(_)));
+
+        // Pattern 3: 检查 init + 属性名的组合是否匹配配置路径
+        for prop in &object_pat.props {
+            if let ObjectPatProp::KeyValue(kv) = prop {
+                // 获取属性名，支持标识符 / 字符串字面量 / 数字字面量三种 key
+                let property_name = prop_name_to_segment(&kv.key);
+
+                if let Some(property_name) = property_name {
+                    // 组合完整路径：init 路径 + 属性名
+                    let full_path = format!("{}.{}", init_path, property_name);
+
+                    // 检查是否匹配配置的路径（精确、glob 或正则）
+                    if self.matched_config_path(&full_path).is_some() {
+                        // Pattern 3: 纯解构（init 不含可选链）
+                        if !has_optional {
+                            match self.mode {
+                                Mode::Report => report_destructuring_error(&full_path, span),
+                                Mode::Fix if !has_rest => {
+                                    self.collect_fix_binding(&kv.value, &full_path);
                                 }
+                                Mode::Fix => {}
                             }
                         }
-                    } else if let ObjectPatProp::Assign(assign) = prop {
-                        // 简写形式：const { env } = process
-                        let property_name = assign.key.sym.to_string();
-                        let full_path = format!("{}.{}", init_path, property_name);
-
-                        if self.config_paths.contains(&full_path) {
-                            if !has_optional {
-                                report_destructuring_error(&full_path, span);
+                    } else if !has_optional && self.mode == Mode::Report {
+                        self.suggest_for_path(&full_path, span);
+                    }
+                }
+            } else if let ObjectPatProp::Assign(assign) = prop {
+                // 简写形式：const { env } = process / const { API_KEY } = process.env
+                let property_name = assign.key.sym.to_string();
+                let full_path = format!("{}.{}", init_path, property_name);
+
+                if self.matched_config_path(&full_path).is_some() {
+                    if !has_optional {
+                        match self.mode {
+                            Mode::Report => report_destructuring_error(&full_path, span),
+                            // 带默认值（`{ API_KEY = 'default' }`）时跳过：直接改写成
+                            // `process.env.API_KEY` 会丢掉默认值表达式及其副作用，
+                            // 因此原样保留这一条绑定，不纳入自动修复。含 `...rest`
+                            // 时同样跳过，见上面 `has_rest` 的注释
+                            Mode::Fix if assign.value.is_none() && !has_rest => {
+                                self.collect_fix_binding(&Pat::Ident(assign.key.clone()), &full_path);
                             }
+                            Mode::Fix => {}
                         }
                     }
+                } else if !has_optional && self.mode == Mode::Report {
+                    self.suggest_for_path(&full_path, span);
                 }
             }
         }